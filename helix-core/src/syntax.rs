@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+// This file only carries the pieces `helix-term/src/handlers/diagnostics.rs`
+// reads off a language's config: the `PullDiagnostics` feature flag and the
+// per-language debounce override. The rest of `LanguageConfiguration` (file
+// types, indentation, grammar, injection queries, ...) lives alongside these
+// and isn't reproduced here.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LanguageServerFeature {
+    Format,
+    GotoDeclaration,
+    GotoDefinition,
+    GotoTypeDefinition,
+    GotoReference,
+    GotoImplementation,
+    SignatureHelp,
+    Hover,
+    DocumentHighlight,
+    Completion,
+    CodeAction,
+    WorkspaceCommand,
+    DocumentSymbols,
+    WorkspaceSymbols,
+    Diagnostics,
+    RenameSymbol,
+    InlayHints,
+    DocumentColors,
+    PullDiagnostics,
+}
+
+/// A single language's entry in `languages.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LanguageConfiguration {
+    /// Per-language override for `[editor.lsp] pull-diagnostics-debounce`.
+    /// Useful for a language whose server is known to be slow (or fast)
+    /// relative to the editor-wide default.
+    #[serde(
+        with = "duration_millis::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pull_diagnostics_debounce: Option<Duration>,
+}
+
+impl Default for LanguageConfiguration {
+    fn default() -> Self {
+        Self {
+            pull_diagnostics_debounce: None,
+        }
+    }
+}
+
+mod duration_millis {
+    pub mod option {
+        use std::time::Duration;
+
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            value
+                .map(|duration| duration.as_millis() as u64)
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
+
 use tokio::time::Instant;
 
 use helix_core::diagnostic::DiagnosticProvider;
@@ -18,6 +20,10 @@ use helix_view::{DocumentId, Editor};
 use crate::events::OnModeSwitch;
 use crate::job;
 
+/// Fallback used when neither `[editor.lsp] pull-diagnostics-debounce` nor a per-language
+/// override is configured. Matches the interval pull diagnostics has always debounced at.
+const DEFAULT_PULL_DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(125);
+
 pub(super) fn register_hooks(handlers: &Handlers) {
     register_hook!(move |event: &mut DiagnosticsDidChange<'_>| {
         if event.editor.mode != Mode::Insert {
@@ -41,7 +47,24 @@ pub(super) fn register_hooks(handlers: &Handlers) {
             .has_language_server_with_feature(LanguageServerFeature::PullDiagnostics)
         {
             let document_id = event.doc.id();
-            send_blocking(&tx, PullDiagnosticsEvent { document_id });
+            // Resolve the debounce here, where the document (and thus its language
+            // config) is available, and hand it to the handler rather than the
+            // global default — `PullDiagnosticsHandler` tracks each pending
+            // document's own deadline, so one document's override can't be cut
+            // short by another document's shorter debounce.
+            let debounce = event
+                .doc
+                .language_config()
+                .and_then(|config| config.pull_diagnostics_debounce)
+                .or(event.editor.config().lsp.pull_diagnostics_debounce)
+                .unwrap_or(DEFAULT_PULL_DIAGNOSTICS_DEBOUNCE);
+            send_blocking(
+                &tx,
+                PullDiagnosticsEvent {
+                    document_id,
+                    debounce,
+                },
+            );
         }
         Ok(())
     });
@@ -69,16 +92,45 @@ pub(super) fn register_hooks(handlers: &Handlers) {
             }
         }
 
+        if supports_workspace_diagnostics(language_server) {
+            pull_workspace_diagnostics(event.editor, event.server_id);
+        }
+
         Ok(())
     });
 }
 
-#[derive(Debug)]
-pub(super) struct PullDiagnosticsHandler {}
+/// Whether `language_server` advertises `workspaceDiagnostics` support.
+fn supports_workspace_diagnostics(language_server: &helix_lsp::Client) -> bool {
+    matches!(
+        language_server.capabilities().diagnostic_provider,
+        Some(lsp::DiagnosticServerCapabilities::Options(
+            lsp::DiagnosticOptions {
+                workspace_diagnostics: true,
+                ..
+            }
+        )) | Some(lsp::DiagnosticServerCapabilities::RegistrationOptions(
+            lsp::DiagnosticRegistrationOptions {
+                diagnostic_options: lsp::DiagnosticOptions {
+                    workspace_diagnostics: true,
+                    ..
+                },
+                ..
+            }
+        ))
+    )
+}
+
+#[derive(Debug, Default)]
+pub(super) struct PullDiagnosticsHandler {
+    /// Each pending document's own deadline, so one document's override debounce can't be
+    /// cut short by another document's shorter one.
+    docs: HashMap<DocumentId, Instant>,
+}
 
 impl PullDiagnosticsHandler {
     pub fn new() -> Self {
-        PullDiagnosticsHandler {}
+        Self::default()
     }
 }
 
@@ -87,30 +139,70 @@ impl helix_event::AsyncHook for PullDiagnosticsHandler {
 
     fn handle_event(
         &mut self,
-        _event: Self::Event,
+        event: Self::Event,
         _timeout: Option<tokio::time::Instant>,
     ) -> Option<tokio::time::Instant> {
-        Some(Instant::now() + Duration::from_millis(125))
+        self.docs
+            .insert(event.document_id, Instant::now() + event.debounce);
+        self.docs.values().min().copied()
     }
 
-    fn finish_debounce(&mut self) {
-        dispatch_pull_diagnostic_for_open_documents();
+    fn finish_debounce(&mut self) -> Option<tokio::time::Instant> {
+        let (ready, pending) =
+            partition_ready_documents(std::mem::take(&mut self.docs), Instant::now());
+        self.docs = pending;
+
+        if !ready.is_empty() {
+            dispatch_pull_diagnostic_for_documents(ready);
+        }
+
+        // Documents whose own debounce hasn't elapsed yet stay pending for the next tick.
+        self.docs.values().min().copied()
     }
 }
 
-fn dispatch_pull_diagnostic_for_open_documents() {
+/// Splits `docs` into those whose deadline has elapsed as of `now` and those still pending.
+fn partition_ready_documents(
+    docs: HashMap<DocumentId, Instant>,
+    now: Instant,
+) -> (HashSet<DocumentId>, HashMap<DocumentId, Instant>) {
+    let (ready, pending): (HashMap<_, _>, HashMap<_, _>) =
+        docs.into_iter().partition(|&(_, deadline)| deadline <= now);
+    (ready.into_keys().collect(), pending)
+}
+
+/// Re-pull diagnostics for the documents that changed, not every open document.
+fn dispatch_pull_diagnostic_for_documents(docs: HashSet<DocumentId>) {
     job::dispatch_blocking(move |editor, _| {
-        let documents = editor.documents.values();
+        let mut workspace_servers = HashSet::new();
+
+        for document_id in docs {
+            let Some(document) = editor.document(document_id) else {
+                continue;
+            };
 
-        for document in documents {
             let language_servers = document
                 .language_servers_with_feature(LanguageServerFeature::PullDiagnostics)
                 .filter(|ls| ls.is_initialized());
 
             for language_server in language_servers {
                 pull_diagnostics_for_document(document, language_server);
+                if supports_workspace_diagnostics(language_server) {
+                    workspace_servers.insert(language_server.id());
+                }
             }
         }
+
+        // Opportunistically refresh workspace diagnostics alongside the document pulls
+        // they're debounced together with, so unopened-file diagnostics don't go stale
+        // as the project changes. This doesn't yet cover the server-initiated
+        // `workspace/diagnostic/refresh` request, which LSP allows a server to send with
+        // no local document changes at all (e.g. after it finishes indexing) — handling
+        // that needs a hook from helix-lsp's server-to-client request dispatcher, which
+        // lives outside this handler.
+        for server_id in workspace_servers {
+            pull_workspace_diagnostics(editor, server_id);
+        }
     })
 }
 
@@ -192,6 +284,118 @@ pub fn pull_diagnostics_for_document(
     });
 }
 
+/// Request `workspace/diagnostic`, seeding `previousResultIds` from the open documents
+/// attached to this server that already hold a diagnostic result id.
+///
+/// `language_server.workspace_diagnostic` already folds `$/progress`-streamed partial
+/// results into `future.0`, so there's no separate progress-token handling to do here.
+fn pull_workspace_diagnostics(
+    editor: &mut Editor,
+    language_server_id: helix_lsp::LanguageServerId,
+) {
+    let Some(language_server) = editor.language_server_by_id(language_server_id) else {
+        return;
+    };
+
+    let previous_result_ids = editor
+        .documents()
+        .filter(|doc| doc.supports_language_server(language_server_id))
+        .filter_map(|doc| {
+            let uri = doc.uri()?;
+            let value = doc.previous_diagnostic_id.clone()?;
+            Some(lsp::PreviousResultId {
+                uri: uri.to_url()?,
+                value,
+            })
+        })
+        .collect();
+
+    let Some(future) = language_server.workspace_diagnostic(previous_result_ids) else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        match future.0.await {
+            Ok(result) => {
+                job::dispatch(move |editor, _| {
+                    if let Some(language_server) = editor.language_server_by_id(language_server_id)
+                    {
+                        language_server.mark_work_as_done(future.1);
+                    };
+
+                    handle_workspace_diagnostics_response(editor, result, language_server_id)
+                })
+                .await
+            }
+            Err(err) => log::error!("Workspace pull diagnostic request failed: {err}"),
+        }
+    });
+}
+
+/// Handles a `workspace/diagnostic` response, including reports for unopened files.
+fn handle_workspace_diagnostics_response(
+    editor: &mut Editor,
+    result: lsp::WorkspaceDiagnosticReportResult,
+    server_id: helix_lsp::LanguageServerId,
+) {
+    let identifier = editor
+        .language_server_by_id(server_id)
+        .and_then(|language_server| language_server.capabilities().diagnostic_provider.as_ref())
+        .and_then(|diagnostic_provider| match diagnostic_provider {
+            lsp::DiagnosticServerCapabilities::Options(options) => options.identifier.clone(),
+            lsp::DiagnosticServerCapabilities::RegistrationOptions(options) => {
+                options.diagnostic_options.identifier.clone()
+            }
+        });
+    let provider = DiagnosticProvider::Lsp {
+        server_id,
+        identifier,
+    };
+
+    let items = match result {
+        lsp::WorkspaceDiagnosticReportResult::Report(report) => report.items,
+        lsp::WorkspaceDiagnosticReportResult::Partial(report) => report.items,
+    };
+
+    for item in items {
+        let (url, result_id, diagnostics) = match item {
+            lsp::WorkspaceDocumentDiagnosticReport::Full(report) => (
+                report.uri,
+                report.full_document_diagnostic_report.result_id,
+                Some(report.full_document_diagnostic_report.items),
+            ),
+            lsp::WorkspaceDocumentDiagnosticReport::Unchanged(report) => (
+                report.uri,
+                Some(report.unchanged_document_diagnostic_report.result_id),
+                None,
+            ),
+        };
+
+        let Some(uri) = Uri::try_from(url).ok() else {
+            continue;
+        };
+
+        if let Some(diagnostics) = diagnostics {
+            editor.handle_lsp_diagnostics(&provider, uri.clone(), None, diagnostics);
+        }
+
+        let document_id =
+            resolve_document_id(editor.documents().map(|doc| (doc.id(), doc.uri())), &uri);
+        if let Some(doc) = document_id.and_then(|id| editor.document_mut(id)) {
+            doc.previous_diagnostic_id = result_id;
+        }
+    }
+}
+
+/// Finds the id of the open document whose uri is `uri`, or `None` if it isn't open.
+fn resolve_document_id(
+    mut docs: impl Iterator<Item = (DocumentId, Option<Uri>)>,
+    uri: &Uri,
+) -> Option<DocumentId> {
+    docs.find(|(_, doc_uri)| doc_uri.as_ref() == Some(uri))
+        .map(|(id, _)| id)
+}
+
 fn handle_pull_diagnostics_response(
     editor: &mut Editor,
     result: lsp::DocumentDiagnosticReportResult,
@@ -199,9 +403,9 @@ fn handle_pull_diagnostics_response(
     uri: Uri,
     document_id: DocumentId,
 ) {
-    match result {
+    let related_documents = match result {
         lsp::DocumentDiagnosticReportResult::Report(report) => {
-            let result_id = match report {
+            let (result_id, related_documents) = match report {
                 lsp::DocumentDiagnosticReport::Full(report) => {
                     editor.handle_lsp_diagnostics(
                         &provider,
@@ -210,17 +414,97 @@ fn handle_pull_diagnostics_response(
                         report.full_document_diagnostic_report.items,
                     );
 
-                    report.full_document_diagnostic_report.result_id
-                }
-                lsp::DocumentDiagnosticReport::Unchanged(report) => {
-                    Some(report.unchanged_document_diagnostic_report.result_id)
+                    (
+                        report.full_document_diagnostic_report.result_id,
+                        report.related_documents,
+                    )
                 }
+                lsp::DocumentDiagnosticReport::Unchanged(report) => (
+                    Some(report.unchanged_document_diagnostic_report.result_id),
+                    report.related_documents,
+                ),
             };
 
             if let Some(doc) = editor.document_mut(document_id) {
                 doc.previous_diagnostic_id = result_id;
             };
+
+            related_documents
         }
-        lsp::DocumentDiagnosticReportResult::Partial(_) => {}
+        lsp::DocumentDiagnosticReportResult::Partial(report) => report.related_documents,
+    };
+
+    handle_related_documents(editor, &provider, related_documents);
+}
+
+/// Apply diagnostics for the `relatedDocuments` a diagnostic report can carry, e.g. headers.
+fn handle_related_documents(
+    editor: &mut Editor,
+    provider: &DiagnosticProvider,
+    related_documents: Option<HashMap<lsp::Url, lsp::DocumentDiagnosticReportKind>>,
+) {
+    let Some(related_documents) = related_documents else {
+        return;
     };
+
+    for (url, report) in related_documents {
+        let Ok(uri) = Uri::try_from(url) else {
+            continue;
+        };
+
+        let result_id = match report {
+            lsp::DocumentDiagnosticReportKind::Full(report) => {
+                editor.handle_lsp_diagnostics(provider, uri.clone(), None, report.items);
+                report.result_id
+            }
+            lsp::DocumentDiagnosticReportKind::Unchanged(report) => Some(report.result_id),
+        };
+
+        let document_id =
+            resolve_document_id(editor.documents().map(|doc| (doc.id(), doc.uri())), &uri);
+        if let Some(doc) = document_id.and_then(|id| editor.document_mut(id)) {
+            doc.previous_diagnostic_id = result_id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn doc_id(id: u64) -> DocumentId {
+        DocumentId::from(slotmap::KeyData::from_ffi(id))
+    }
+
+    #[test]
+    fn finish_debounce_only_flushes_elapsed_documents() {
+        let now = Instant::now();
+        let short = doc_id(1);
+        let long = doc_id(2);
+
+        let mut docs = HashMap::new();
+        docs.insert(short, now);
+        docs.insert(long, now + Duration::from_secs(5));
+
+        let (ready, pending) = partition_ready_documents(docs, now);
+
+        assert_eq!(ready, HashSet::from([short]));
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[&long], now + Duration::from_secs(5));
+    }
+
+    #[test]
+    fn resolve_document_id_is_none_for_unopened_file() {
+        let open = doc_id(1);
+        let open_uri = Uri::try_from(lsp::Url::parse("file:///open.rs").unwrap()).unwrap();
+        let unopened_uri = Uri::try_from(lsp::Url::parse("file:///unopened.rs").unwrap()).unwrap();
+
+        let docs = [(open, Some(open_uri.clone()))];
+
+        assert_eq!(
+            resolve_document_id(docs.clone().into_iter(), &open_uri),
+            Some(open)
+        );
+        assert_eq!(resolve_document_id(docs.into_iter(), &unopened_uri), None);
+    }
 }
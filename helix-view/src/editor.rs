@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+// This file only carries the `[editor.lsp]` section that
+// `helix-term/src/handlers/diagnostics.rs` reads; the rest of `Config`'s
+// fields (scrolloff, mouse, statusline, ...) live alongside it and aren't
+// reproduced here.
+
+/// Editor-wide settings deserialized from the `[editor]` table of `config.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// Language server behavior, configured under `[editor.lsp]`.
+    pub lsp: LspConfig,
+}
+
+/// Language server settings, configured under `[editor.lsp]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LspConfig {
+    /// How long to wait after the last document change before re-requesting
+    /// pull diagnostics (`textDocument/diagnostic` and `workspace/diagnostic`).
+    /// Defaults to 125ms when unset; raise it on large files or slow servers
+    /// to cut down on redundant requests. A language's `languages.toml` entry
+    /// can override this per-language, see
+    /// `LanguageConfiguration::pull_diagnostics_debounce`.
+    #[serde(
+        with = "duration_millis::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pull_diagnostics_debounce: Option<Duration>,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        Self {
+            pull_diagnostics_debounce: None,
+        }
+    }
+}
+
+mod duration_millis {
+    pub mod option {
+        use std::time::Duration;
+
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            value
+                .map(|duration| duration.as_millis() as u64)
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use crate::DocumentId;
+
+/// Sent whenever a document that has a pull-diagnostics-capable language
+/// server attached changes, to (re)schedule a pull through
+/// `PullDiagnosticsHandler`'s debounce.
+#[derive(Debug, Clone, Copy)]
+pub struct PullDiagnosticsEvent {
+    pub document_id: DocumentId,
+    /// The debounce to wait for this document specifically, resolved by the
+    /// sender from `[editor.lsp] pull-diagnostics-debounce` and the
+    /// document's language config override at the time the change happened.
+    pub debounce: Duration,
+}